@@ -1,7 +1,123 @@
 use std::fs;
-use std::path::Path;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use image::codecs::avif::AvifEncoder;
+use image::imageops::FilterType;
 use image::ImageFormat;
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
 use walkdir::WalkDir;
+use webp::Encoder;
+
+/// The image format(s) a source image should be converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Convert to WebP only (the converter's original behavior).
+    WebP,
+    /// Convert to AVIF only.
+    Avif,
+    /// Convert to both WebP and AVIF.
+    Both,
+}
+
+/// The outcome of a batch [`WebPConverter::convert`] run.
+#[derive(Debug, Clone, Default)]
+pub struct ConversionSummary {
+    /// Output files that were written successfully (a source converted with
+    /// [`OutputFormat::Both`] contributes both its `.webp` and `.avif` paths).
+    pub successes: Vec<PathBuf>,
+    /// Source files that failed, paired with their error message.
+    pub failures: Vec<(PathBuf, String)>,
+    /// Output paths of originals that were copied through unchanged because their
+    /// encoded WebP wasn't smaller than the source. Only populated when
+    /// [`WebPConverter::with_skip_if_larger`] is enabled.
+    pub kept_original: Vec<PathBuf>,
+}
+
+/// What [`WebPConverter::convert_to`] actually did for a single source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionOutcome {
+    /// The image was encoded and written to `path`.
+    Converted(PathBuf),
+    /// The encoded image wasn't smaller than the original by the configured
+    /// threshold, so the original was copied through unchanged to `path`
+    /// instead, under its own original extension (not `.webp`).
+    KeptOriginal(PathBuf),
+    /// [`OutputFormat::Both`] was requested: the WebP side's own outcome
+    /// (either [`Converted`](Self::Converted) or
+    /// [`KeptOriginal`](Self::KeptOriginal)), paired with the path the AVIF
+    /// sibling was written to.
+    Both(Box<ConversionOutcome>, PathBuf),
+}
+
+/// Folds a [`ConversionOutcome`] into a [`ConversionSummary`], recursing into
+/// [`ConversionOutcome::Both`] so each side is recorded individually.
+fn record_outcome(summary: &mut ConversionSummary, outcome: ConversionOutcome) {
+    match outcome {
+        ConversionOutcome::Converted(path) => summary.successes.push(path),
+        ConversionOutcome::KeptOriginal(path) => summary.kept_original.push(path),
+        ConversionOutcome::Both(webp, avif) => {
+            record_outcome(summary, *webp);
+            summary.successes.push(avif);
+        }
+    }
+}
+
+/// A single resized WebP rendition produced for responsive `<img srcset>` use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponsiveVariant {
+    /// The target width, in pixels, this variant was resized to.
+    pub width: u32,
+    /// Where the resized WebP file was written.
+    pub path: PathBuf,
+}
+
+/// The set of responsive variants generated for a single source image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponsiveManifest {
+    /// The source image these variants were generated from.
+    pub source: PathBuf,
+    /// The generated variants, in the order their widths were requested.
+    pub variants: Vec<ResponsiveVariant>,
+}
+
+impl ResponsiveManifest {
+    /// Builds a `srcset` attribute value from the manifest's variants, e.g.
+    /// `"photo-320w.webp 320w, photo-640w.webp 640w"`.
+    pub fn to_srcset(&self) -> String {
+        self.variants
+            .iter()
+            .map(|variant| format!("{} {}w", variant.path.display(), variant.width))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Builds a `<picture>` element with a WebP `srcset` and a `<img>` fallback,
+    /// ready to drop into a page.
+    ///
+    /// `fallback_src` is used as the `<img src>` (typically the original image
+    /// or the largest variant) and `alt` becomes the `<img alt>` text. The
+    /// `srcset`, `src` and `alt` values are all HTML-escaped before being
+    /// interpolated into the markup.
+    pub fn to_picture_html(&self, fallback_src: &str, alt: &str) -> String {
+        format!(
+            "<picture>\n  <source type=\"image/webp\" srcset=\"{}\">\n  <img src=\"{}\" alt=\"{}\">\n</picture>",
+            escape_html(&self.to_srcset()),
+            escape_html(fallback_src),
+            escape_html(alt)
+        )
+    }
+}
+
+/// Escapes the characters that would break or corrupt an HTML attribute value.
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
 
 /// A struct for converting image files to WebP format.
 ///
@@ -15,9 +131,13 @@ use walkdir::WalkDir;
 ///
 /// The `WebPConverter` struct allows you to:
 /// - Specify source and output directories for batch conversion
-/// - Convert multiple image formats (JPG, JPEG, PNG, GIF) to WebP
+/// - Convert multiple image formats (JPG, JPEG, PNG, GIF) to WebP and/or AVIF
 /// - Preserve the directory structure of the source in the output
-/// - Handle errors during the conversion process
+/// - Control lossy compression via an adjustable quality setting
+/// - Restrict which extensions are converted and whether subdirectories are descended
+/// - Generate resized WebP variants with a ready-to-use `srcset`/`<picture>` manifest
+/// - Convert large batches in parallel with progress feedback and per-file results
+/// - Optionally keep the original file when the encoded WebP isn't actually smaller
 ///
 /// # Examples
 ///
@@ -56,6 +176,14 @@ use walkdir::WalkDir;
 pub struct WebPConverter {
     source_dir: String,
     output_dir: String,
+    quality: f32,
+    output_format: OutputFormat,
+    avif_quality: u8,
+    avif_speed: u8,
+    extensions: Vec<String>,
+    recursive: bool,
+    skip_if_larger: bool,
+    size_threshold: f32,
 }
 
 impl WebPConverter {
@@ -91,19 +219,132 @@ impl WebPConverter {
         WebPConverter {
             source_dir: source_dir.to_string(),
             output_dir: output_dir.to_string(),
+            quality: 100.0,
+            output_format: OutputFormat::WebP,
+            avif_quality: 80,
+            avif_speed: 6,
+            extensions: vec!["jpg".to_string(), "jpeg".to_string(), "png".to_string(), "gif".to_string()],
+            recursive: true,
+            skip_if_larger: false,
+            size_threshold: 0.0,
         }
     }
 
-    /// Converts all supported image files in the source directory to WebP format.
+    /// Sets the WebP encoding quality.
+    ///
+    /// `quality` ranges from `0` (smallest, lowest quality) to `100` (largest,
+    /// highest quality). A value of `100` selects lossless encoding, matching
+    /// the converter's previous behavior. Any other value is encoded as lossy
+    /// WebP via the `webp` crate, which trades quality for file size much
+    /// like `cwebp -q` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use webp_converter::WebPConverter;
+    ///
+    /// let converter = WebPConverter::new("path/to/source", "path/to/output")
+    ///     .with_quality(80.0);
+    /// ```
+    pub fn with_quality(mut self, quality: f32) -> Self {
+        self.quality = quality.clamp(0.0, 100.0);
+        self
+    }
+
+    /// Sets which format(s) images are converted to.
+    ///
+    /// Defaults to [`OutputFormat::WebP`]. Use [`OutputFormat::Avif`] to emit
+    /// AVIF instead, or [`OutputFormat::Both`] to emit a `.webp` and `.avif`
+    /// sibling for each source image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use webp_converter::{OutputFormat, WebPConverter};
+    ///
+    /// let converter = WebPConverter::new("path/to/source", "path/to/output")
+    ///     .with_format(OutputFormat::Both);
+    /// ```
+    pub fn with_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// Sets the AVIF encoding quality (`0`-`100`, higher is better).
+    pub fn with_avif_quality(mut self, avif_quality: u8) -> Self {
+        self.avif_quality = avif_quality;
+        self
+    }
+
+    /// Sets the AVIF encoding speed (`0`-`10`, lower is slower but smaller).
+    pub fn with_avif_speed(mut self, avif_speed: u8) -> Self {
+        self.avif_speed = avif_speed;
+        self
+    }
+
+    /// Restricts which source file extensions are considered for conversion.
+    ///
+    /// Defaults to `["jpg", "jpeg", "png", "gif"]`. Extensions are matched
+    /// case-insensitively and without a leading dot (e.g. `"png"`, not `".png"`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use webp_converter::WebPConverter;
+    ///
+    /// let converter = WebPConverter::new("path/to/source", "path/to/output")
+    ///     .with_extensions(&["png", "jpg"]);
+    /// ```
+    pub fn with_extensions(mut self, extensions: &[&str]) -> Self {
+        self.extensions = extensions.iter().map(|ext| ext.to_lowercase()).collect();
+        self
+    }
+
+    /// Sets whether subdirectories of the source directory are descended into.
+    ///
+    /// Defaults to `true`. Pass `false` to only convert files directly inside
+    /// `source_dir`.
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Enables skipping (keeping the original file) when the encoded WebP isn't
+    /// actually smaller than the source image.
+    ///
+    /// Defaults to `false`. Combine with [`Self::with_size_threshold`] to require
+    /// more than a token reduction before the WebP is kept.
+    pub fn with_skip_if_larger(mut self, skip_if_larger: bool) -> Self {
+        self.skip_if_larger = skip_if_larger;
+        self
+    }
+
+    /// Sets the minimum fractional size reduction required to keep an encoded
+    /// WebP, as a value between `0.0` and `1.0`.
+    ///
+    /// For example, `0.1` requires the WebP to be more than 10% smaller than the
+    /// source; otherwise the original is kept. Only takes effect when
+    /// [`Self::with_skip_if_larger`] is enabled. Defaults to `0.0` (any
+    /// reduction, however small, is accepted).
+    pub fn with_size_threshold(mut self, size_threshold: f32) -> Self {
+        self.size_threshold = size_threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Converts all supported image files in the source directory, in parallel.
     ///
     /// This method walks through the source directory, identifies supported image files
-    /// (JPG, JPEG, PNG, GIF), and converts them to WebP format in the output directory.
-    /// It preserves the directory structure of the source in the output.
+    /// (JPG, JPEG, PNG, GIF by default), and converts each to the configured
+    /// [`OutputFormat`] (WebP by default) in the output directory, preserving the
+    /// directory structure of the source. Candidate files are converted concurrently
+    /// via `rayon` while a progress bar tracks overall completion, so one bad file
+    /// no longer aborts the whole batch.
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the conversion process completes successfully, or an error
-    /// if any issues occur during the process.
+    /// A [`ConversionSummary`] listing every file that succeeded and every file that
+    /// failed (with its error message). Only I/O errors encountered while listing the
+    /// source directory itself are returned as an `Err`.
     ///
     /// # Examples
     ///
@@ -113,100 +354,244 @@ impl WebPConverter {
     /// use webp_converter::WebPConverter;
     ///
     /// let converter = WebPConverter::new("path/to/source", "path/to/output");
-    /// match converter.convert() {
-    ///     Ok(_) => println!("Conversion completed successfully"),
-    ///     Err(e) => eprintln!("Error during conversion: {}", e),
-    /// }
+    /// let summary = converter.convert().unwrap();
+    /// println!("{} converted, {} failed", summary.successes.len(), summary.failures.len());
     /// ```
     ///
-    /// Converting and processing results:
+    /// Inspecting failures:
     ///
     /// ```no_run
     /// use webp_converter::WebPConverter;
     ///
     /// let converter = WebPConverter::new("path/to/source", "path/to/output");
-    /// if let Err(e) = converter.convert() {
-    ///     eprintln!("Conversion failed: {}", e);
-    ///     // Handle the error (e.g., log it, notify user, etc.)
-    /// } else {
-    ///     println!("All images converted successfully");
-    ///     // Perform post-conversion tasks (e.g., update database, notify user, etc.)
+    /// let summary = converter.convert().unwrap();
+    /// for (path, error) in &summary.failures {
+    ///     eprintln!("{:?} failed: {}", path, error);
     /// }
     /// ```
-    pub fn convert(&self) -> Result<(), Box<dyn std::error::Error>> {
-        for entry in WalkDir::new(&self.source_dir) {
+    pub fn convert(&self) -> Result<ConversionSummary, Box<dyn std::error::Error>> {
+        let mut walker = WalkDir::new(&self.source_dir);
+        if !self.recursive {
+            walker = walker.max_depth(1);
+        }
+
+        let mut candidates = Vec::new();
+        for entry in walker {
             let entry = entry?;
             if entry.file_type().is_file() {
                 let path = entry.path();
                 if let Some(extension) = path.extension() {
                     if let Some(ext) = extension.to_str() {
-                        if ["jpg", "jpeg", "png", "gif"].contains(&ext.to_lowercase().as_str()) {
-                            self.convert_to_webp(path)?;
+                        if self.extensions.iter().any(|e| e == &ext.to_lowercase()) {
+                            candidates.push(path.to_path_buf());
                         }
                     }
                 }
             }
         }
-        Ok(())
+
+        let progress = ProgressBar::new(candidates.len() as u64);
+        progress.set_style(
+            ProgressStyle::with_template("{bar:40} {pos}/{len} {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar()),
+        );
+
+        let results: Vec<Result<ConversionOutcome, (PathBuf, String)>> = candidates
+            .par_iter()
+            .map(|path| {
+                let result = self
+                    .convert_to(path, self.output_format)
+                    .map_err(|e| (path.clone(), e.to_string()));
+                progress.inc(1);
+                result
+            })
+            .collect();
+        progress.finish_and_clear();
+
+        let mut summary = ConversionSummary::default();
+        for result in results {
+            match result {
+                Ok(outcome) => record_outcome(&mut summary, outcome),
+                Err(failure) => summary.failures.push(failure),
+            }
+        }
+        Ok(summary)
     }
 
-    /// Converts a single image file to WebP format.
+    /// Converts a single image file to the given output format(s).
     ///
-    /// This method takes a path to an image file, converts it to WebP format, and saves
-    /// the result in the output directory. It preserves the relative path structure
+    /// This method takes a path to an image file, converts it to `format`, and saves
+    /// the result(s) in the output directory. It preserves the relative path structure
     /// from the source directory.
     ///
     /// # Arguments
     ///
     /// * `path` - The path to the image file to be converted.
+    /// * `format` - Which format(s) to emit for this image.
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the conversion is successful, or an error if any issues occur.
+    /// A [`ConversionOutcome`] describing whether the image was converted or, when
+    /// [`Self::with_skip_if_larger`] is enabled and the encoded WebP wasn't smaller
+    /// than the source, whether the original was kept instead. Returns an error if
+    /// `path` isn't inside `source_dir`, or if any other issue occurs.
     ///
     /// # Examples
     ///
-    /// Converting a single image:
+    /// Converting a single image to both WebP and AVIF:
     ///
     /// ```no_run
     /// use std::path::Path;
-    /// use webp_converter::WebPConverter;
+    /// use webp_converter::{OutputFormat, WebPConverter};
     ///
     /// let converter = WebPConverter::new("path/to/source", "path/to/output");
     /// let image_path = Path::new("path/to/image.jpg");
-    /// match converter.convert_to_webp(image_path) {
-    ///     Ok(_) => println!("Image converted successfully"),
+    /// match converter.convert_to(image_path, OutputFormat::Both) {
+    ///     Ok(outcome) => println!("{:?}", outcome),
     ///     Err(e) => eprintln!("Error converting image: {}", e),
     /// }
     /// ```
+    pub fn convert_to(&self, path: &Path, format: OutputFormat) -> Result<ConversionOutcome, Box<dyn std::error::Error>> {
+        let img = image::open(path)?;
+        let relative_path = path.strip_prefix(&self.source_dir).map_err(|_| {
+            format!(
+                "{} is not inside source directory {}",
+                path.display(),
+                self.source_dir
+            )
+        })?;
+        let mut webp_outcome = None;
+        let mut avif_path = None;
+
+        if matches!(format, OutputFormat::WebP | OutputFormat::Both) {
+            let output_path = Path::new(&self.output_dir).join(relative_path).with_extension("webp");
+            fs::create_dir_all(output_path.parent().unwrap())?;
+
+            let encoded: Vec<u8> = if self.quality >= 100.0 {
+                let mut buf = Cursor::new(Vec::new());
+                img.write_to(&mut buf, ImageFormat::WebP)?;
+                buf.into_inner()
+            } else {
+                let encoder = Encoder::from_image(&img)?;
+                encoder.encode(self.quality).to_vec()
+            };
+
+            if self.skip_if_larger {
+                let original_len = fs::metadata(path)?.len() as f32;
+                let max_allowed_len = original_len * (1.0 - self.size_threshold);
+                if encoded.len() as f32 >= max_allowed_len {
+                    // Keep the original bytes under their own extension rather than
+                    // `output_path`, which is suffixed `.webp` — writing raw source
+                    // bytes there would produce a mislabeled, undecodable file.
+                    let kept_path = Path::new(&self.output_dir).join(relative_path);
+                    fs::create_dir_all(kept_path.parent().unwrap())?;
+                    fs::copy(path, &kept_path)?;
+                    webp_outcome = Some(ConversionOutcome::KeptOriginal(kept_path));
+                }
+            }
+
+            if webp_outcome.is_none() {
+                fs::write(&output_path, &encoded)?;
+                webp_outcome = Some(ConversionOutcome::Converted(output_path));
+            }
+        }
+
+        if matches!(format, OutputFormat::Avif | OutputFormat::Both) {
+            let output_path = Path::new(&self.output_dir).join(relative_path).with_extension("avif");
+            fs::create_dir_all(output_path.parent().unwrap())?;
+
+            let mut output_file = fs::File::create(&output_path)?;
+            let encoder = AvifEncoder::new_with_speed_quality(&mut output_file, self.avif_speed, self.avif_quality);
+            img.write_with_encoder(encoder)?;
+
+            avif_path = Some(output_path);
+        }
+
+        // Report both sides of a `Both` conversion individually so callers (and
+        // the `ConversionSummary` built from them) can tell whether the AVIF
+        // sibling was written, not just the WebP outcome.
+        match (webp_outcome, avif_path) {
+            (Some(webp), Some(avif)) => Ok(ConversionOutcome::Both(Box::new(webp), avif)),
+            (Some(webp), None) => Ok(webp),
+            (None, Some(avif)) => Ok(ConversionOutcome::Converted(avif)),
+            (None, None) => Ok(ConversionOutcome::Converted(path.to_path_buf())),
+        }
+    }
+
+    /// Generates resized WebP variants of a single source image for responsive
+    /// `<img srcset>` delivery.
+    ///
+    /// For each width in `widths`, the image is downscaled (using Lanczos3
+    /// filtering) to that width while preserving its aspect ratio, then saved
+    /// as `{name}-{width}w.webp` next to where [`Self::convert_to`] would place
+    /// the full-size conversion. Widths larger than the source's own width are
+    /// skipped, since this API never upscales.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the source image.
+    /// * `widths` - The target widths, in pixels, to generate.
+    ///
+    /// # Returns
+    ///
+    /// A [`ResponsiveManifest`] listing the variants that were generated, in
+    /// the same order as `widths` (skipping any that were too large). Returns
+    /// an error if `path` isn't inside `source_dir`.
     ///
-    /// Handling conversion errors for multiple images:
+    /// # Examples
     ///
     /// ```no_run
     /// use std::path::Path;
     /// use webp_converter::WebPConverter;
     ///
     /// let converter = WebPConverter::new("path/to/source", "path/to/output");
-    /// let images = vec![
-    ///     Path::new("image1.png"),
-    ///     Path::new("image2.jpg"),
-    ///     Path::new("image3.gif"),
-    /// ];
-    ///
-    /// for image in images {
-    ///     match converter.convert_to_webp(image) {
-    ///         Ok(_) => println!("{:?} converted successfully", image),
-    ///         Err(e) => eprintln!("Error converting {:?}: {}", image, e),
-    ///     }
-    /// }
+    /// let manifest = converter
+    ///     .generate_responsive(Path::new("path/to/source/photo.jpg"), &[320, 640, 1024])
+    ///     .unwrap();
+    /// println!("{}", manifest.to_srcset());
     /// ```
-    pub fn convert_to_webp(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn generate_responsive(
+        &self,
+        path: &Path,
+        widths: &[u32],
+    ) -> Result<ResponsiveManifest, Box<dyn std::error::Error>> {
         let img = image::open(path)?;
-        let file_name = path.file_name().unwrap().to_str().unwrap();
-        let output_path = Path::new(&self.output_dir).join(file_name).with_extension("webp");
-        fs::create_dir_all(output_path.parent().unwrap())?;
-        img.save_with_format(output_path, ImageFormat::WebP)?;
-        Ok(())
+        let relative_path = path.strip_prefix(&self.source_dir).map_err(|_| {
+            format!(
+                "{} is not inside source directory {}",
+                path.display(),
+                self.source_dir
+            )
+        })?;
+        let stem = path.file_stem().unwrap().to_str().unwrap();
+
+        let mut variants = Vec::new();
+        for &width in widths {
+            if width > img.width() {
+                continue;
+            }
+
+            let height = ((img.height() as f64 * width as f64 / img.width() as f64).round()) as u32;
+            let resized = img.resize_exact(width, height, FilterType::Lanczos3);
+
+            let file_name = format!("{}-{}w.webp", stem, width);
+            let output_path = Path::new(&self.output_dir)
+                .join(relative_path.parent().unwrap_or_else(|| Path::new("")))
+                .join(file_name);
+            fs::create_dir_all(output_path.parent().unwrap())?;
+
+            if self.quality >= 100.0 {
+                resized.save_with_format(&output_path, ImageFormat::WebP)?;
+            } else {
+                let encoder = Encoder::from_image(&resized)?;
+                let encoded = encoder.encode(self.quality);
+                fs::write(&output_path, &*encoded)?;
+            }
+
+            variants.push(ResponsiveVariant { width, path: output_path });
+        }
+
+        Ok(ResponsiveManifest { source: path.to_path_buf(), variants })
     }
 }
 
@@ -218,16 +603,19 @@ mod tests {
     #[test]
     fn test_webp_conversion() {
         let source_dir = PathBuf::from("src/tests/source");
-        let output_dir = PathBuf::from("src/tests/dist");
+        let output_dir = PathBuf::from("src/tests/dist/test_webp_conversion");
 
         let converter = WebPConverter::new(
             source_dir.to_str().unwrap(),
             output_dir.to_str().unwrap(),
-        );
+        )
+        .with_recursive(false);
 
-        converter.convert().unwrap();
+        let summary = converter.convert().unwrap();
+        assert!(summary.failures.is_empty(), "Unexpected failures: {:?}", summary.failures);
 
-        // Check if WebP files were created
+        // Check if WebP files were created. Non-recursive, so the nested
+        // fixture used by `test_directory_structure_preserved` is excluded.
         let expected_files = [
             "ferris_jpg.webp",
             "ferris_jpeg.webp",
@@ -256,7 +644,7 @@ mod tests {
     #[test]
     fn test_single_file_conversion() {
         let source_dir = PathBuf::from("src/tests/source");
-        let output_dir = PathBuf::from("src/tests/dist");
+        let output_dir = PathBuf::from("src/tests/dist/test_single_file_conversion");
 
         let converter = WebPConverter::new(
             source_dir.to_str().unwrap(),
@@ -264,9 +652,165 @@ mod tests {
         );
 
         let single_file = source_dir.join("ferris_jpg.jpg");
-        converter.convert_to_webp(&single_file).unwrap();
+        converter.convert_to(&single_file, OutputFormat::WebP).unwrap();
 
         let output_file = output_dir.join("ferris_jpg.webp");
         assert!(output_file.exists(), "WebP file not created");
     }
+
+    #[test]
+    fn test_directory_structure_preserved() {
+        let source_dir = PathBuf::from("src/tests/source");
+        let output_dir = PathBuf::from("src/tests/dist/test_directory_structure_preserved");
+
+        let converter = WebPConverter::new(
+            source_dir.to_str().unwrap(),
+            output_dir.to_str().unwrap(),
+        );
+
+        let nested_file = source_dir.join("nested/ferris_jpg.jpg");
+        converter.convert_to(&nested_file, OutputFormat::WebP).unwrap();
+
+        let output_file = output_dir.join("nested/ferris_jpg.webp");
+        assert!(output_file.exists(), "Nested WebP file not found in preserved subdirectory");
+    }
+
+    #[test]
+    fn test_skip_if_larger_keeps_original() {
+        let source_dir = PathBuf::from("src/tests/source");
+        let output_dir = PathBuf::from("src/tests/dist/test_skip_if_larger_keeps_original");
+
+        let converter = WebPConverter::new(
+            source_dir.to_str().unwrap(),
+            output_dir.to_str().unwrap(),
+        )
+        .with_skip_if_larger(true)
+        .with_size_threshold(1.0);
+
+        let single_file = source_dir.join("ferris_jpg.jpg");
+        let outcome = converter.convert_to(&single_file, OutputFormat::WebP).unwrap();
+
+        match outcome {
+            ConversionOutcome::KeptOriginal(path) => {
+                assert!(path.exists(), "Kept-original file not found");
+                assert_eq!(
+                    path.extension().and_then(|ext| ext.to_str()),
+                    Some("jpg"),
+                    "Kept-original file should keep its source extension, not .webp"
+                );
+                assert_eq!(
+                    fs::read(&path).unwrap(),
+                    fs::read(&single_file).unwrap(),
+                    "Kept-original file should be byte-identical to the source"
+                );
+            }
+            other => panic!("Expected KeptOriginal, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_generate_responsive_variants() {
+        let source_dir = PathBuf::from("src/tests/source");
+        let output_dir = PathBuf::from("src/tests/dist/test_generate_responsive_variants");
+
+        let converter = WebPConverter::new(
+            source_dir.to_str().unwrap(),
+            output_dir.to_str().unwrap(),
+        );
+
+        let single_file = source_dir.join("ferris_jpg.jpg");
+        let manifest = converter.generate_responsive(&single_file, &[32, 64]).unwrap();
+
+        assert_eq!(manifest.variants.len(), 2, "Expected a variant per requested width");
+        for variant in &manifest.variants {
+            assert!(variant.path.exists(), "Variant {:?} not found", variant.path);
+        }
+        assert!(manifest.to_srcset().contains("32w"));
+    }
+
+    #[test]
+    fn test_picture_html_escapes_attributes() {
+        let manifest = ResponsiveManifest {
+            source: PathBuf::from("photo.jpg"),
+            variants: vec![ResponsiveVariant {
+                width: 320,
+                path: PathBuf::from("photo-320w.webp?a=\"1\"&b=2"),
+            }],
+        };
+
+        let html = manifest.to_picture_html("photo.jpg?a=\"1\"&b=2", "A <cute> \"dog\"");
+
+        assert!(html.contains("&quot;"), "Quotes should be escaped: {html}");
+        assert!(html.contains("&lt;cute&gt;"), "Angle brackets should be escaped: {html}");
+        assert!(
+            html.matches("&amp;b=2").count() >= 2,
+            "Ampersands in both the srcset and the src should be escaped: {html}"
+        );
+        assert!(!html.contains("<cute>"), "Raw unescaped markup leaked into the output: {html}");
+        assert!(!html.contains("webp?a=\"1\"&b"), "Raw unescaped srcset leaked into the output: {html}");
+    }
+
+    #[test]
+    fn test_avif_conversion() {
+        let source_dir = PathBuf::from("src/tests/source");
+        let output_dir = PathBuf::from("src/tests/dist/test_avif_conversion");
+
+        let converter = WebPConverter::new(
+            source_dir.to_str().unwrap(),
+            output_dir.to_str().unwrap(),
+        )
+        .with_format(OutputFormat::Avif);
+
+        let single_file = source_dir.join("ferris_jpg.jpg");
+        converter.convert_to(&single_file, OutputFormat::Avif).unwrap();
+
+        let output_file = output_dir.join("ferris_jpg.avif");
+        assert!(output_file.exists(), "AVIF file not created");
+    }
+
+    #[test]
+    fn test_both_format_reports_each_side() {
+        let source_dir = PathBuf::from("src/tests/source");
+        let output_dir = PathBuf::from("src/tests/dist/test_both_format_reports_each_side");
+
+        let converter = WebPConverter::new(
+            source_dir.to_str().unwrap(),
+            output_dir.to_str().unwrap(),
+        )
+        .with_format(OutputFormat::Both);
+
+        let single_file = source_dir.join("ferris_jpg.jpg");
+        let outcome = converter.convert_to(&single_file, OutputFormat::Both).unwrap();
+
+        match outcome.clone() {
+            ConversionOutcome::Both(webp, avif) => {
+                assert!(matches!(*webp, ConversionOutcome::Converted(_)));
+                assert!(avif.exists(), "AVIF sibling not found");
+                assert_eq!(avif.extension().and_then(|ext| ext.to_str()), Some("avif"));
+            }
+            other => panic!("Expected Both, got {:?}", other),
+        }
+
+        let mut summary = ConversionSummary::default();
+        record_outcome(&mut summary, outcome);
+        assert_eq!(summary.successes.len(), 2, "Both the WebP and AVIF outputs should be recorded");
+    }
+
+    #[test]
+    fn test_lossy_quality_conversion() {
+        let source_dir = PathBuf::from("src/tests/source");
+        let output_dir = PathBuf::from("src/tests/dist/test_lossy_quality_conversion");
+
+        let converter = WebPConverter::new(
+            source_dir.to_str().unwrap(),
+            output_dir.to_str().unwrap(),
+        )
+        .with_quality(75.0);
+
+        let single_file = source_dir.join("ferris_jpg.jpg");
+        converter.convert_to(&single_file, OutputFormat::WebP).unwrap();
+
+        let output_file = output_dir.join("ferris_jpg.webp");
+        assert!(output_file.exists(), "Lossy WebP file not created");
+    }
 }